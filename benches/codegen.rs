@@ -0,0 +1,37 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use pg2rs::{render_synthetic, Options, TIMESTAMP_CHRONO};
+
+/// Tables of a large schema, each with 20 columns of mixed types and enum columns
+const TABLES: [usize; 2] = [200, 2000];
+const COLUMNS: usize = 20;
+const ENUMS: usize = 50;
+
+fn codegen(c: &mut Criterion) {
+    let mut group = c.benchmark_group("render");
+    group.sample_size(20);
+    let defaults = Options::default();
+    let all_impls = Options {
+        timestamp_type: String::from(TIMESTAMP_CHRONO),
+        serde: true,
+        all_optional: true,
+        emit_insert_structs: true,
+        emit_builder: true,
+        emit_fields: true,
+        emit_metadata: true,
+        tuple_conversions: true,
+        typed_ids: true,
+        ..Options::default()
+    };
+    for tables in TABLES {
+        group.bench_with_input(BenchmarkId::new("defaults", tables), &tables, | b, tables | {
+            b.iter(|| render_synthetic(&defaults, *tables, COLUMNS, ENUMS).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("all_impls", tables), &tables, | b, tables | {
+            b.iter(|| render_synthetic(&all_impls, *tables, COLUMNS, ENUMS).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, codegen);
+criterion_main!(benches);
@@ -0,0 +1,189 @@
+use crate::{
+    Error, Options, CRATE_POSTGRES, CRATE_SQLX, CRATE_TOKIO_POSTGRES, CRATE_VERSIONS,
+    OUTPUT_JSON, OUTPUT_RUST, OUTPUT_SQL, TIMESTAMP_CHRONO, TIMESTAMP_STRING, TIMESTAMP_TIME,
+    DECIMAL_BIGDECIMAL, DECIMAL_RUST_DECIMAL, DECIMAL_STRING
+};
+use std::collections::BTreeMap;
+
+/// Named values of a smallint column which should be generated as an enum
+#[derive(Debug, Clone, Default)]
+pub struct SmallintEnum {
+    /// values are checked against the column type once it is introspected
+    pub variants: Vec<(i64, String)>,
+}
+
+/// table name -> column name -> enum values
+pub type SmallintEnums = BTreeMap<String, BTreeMap<String, SmallintEnum>>;
+
+/// Parses a smallint enums mapping file in a form
+/// `table.column = { 0 = "Inactive", 1 = "Active" }`
+pub fn parse_smallint_enums(content: &str) -> Result<SmallintEnums, Error> {
+    let value: toml::Value = toml::from_str(content)
+        .map_err(|e| Error::InvalidConfig(e.to_string()))?;
+    let mut result = SmallintEnums::new();
+    for (table_name, columns) in as_table(&value, "smallint enums")? {
+        for (column_name, values) in as_table(columns, table_name)? {
+            let mut smallint_enum = SmallintEnum::default();
+            for (value, label) in as_table(values, column_name)? {
+                let value = value.parse::<i64>().map_err(|_| Error::InvalidConfig(format!(
+                    "{}.{}: '{}' is not an integer value", table_name, column_name, value)))?;
+                let label = label.as_str().ok_or_else(|| Error::InvalidConfig(format!(
+                    "{}.{}: label for {} must be a string", table_name, column_name, value)))?;
+                smallint_enum.variants.push((value, String::from(label)));
+            }
+            smallint_enum.variants.sort();
+            result.entry(table_name.clone()).or_default()
+                .insert(column_name.clone(), smallint_enum);
+        }
+    }
+    Ok(result)
+}
+
+/// Parses explicit singular forms overriding inflection, in a form `people = "person"`
+pub fn parse_singular_rules(content: &str) -> Result<BTreeMap<String, String>, Error> {
+    let value: toml::Value = toml::from_str(content)
+        .map_err(|e| Error::InvalidConfig(e.to_string()))?;
+    as_table(&value, "singular rules")?.iter().map(| (plural, singular) | {
+        singular.as_str()
+            .map(| singular | (plural.clone(), String::from(singular)))
+            .ok_or_else(|| Error::InvalidConfig(format!(
+                "{}: singular form must be a string", plural)))
+    }).collect()
+}
+
+/// One of the `[[outputs]]` generated from a single introspection,
+/// unset options are taken from the command line
+#[derive(Debug, Clone, Default)]
+pub struct OutputSpec {
+    pub output_file: String,
+    pub postgres_crate: Option<String>,
+    pub crate_version: Option<String>,
+    pub output_format: Option<String>,
+    pub serde: Option<bool>,
+    pub use_chrono_crate: Option<bool>,
+    pub timestamp_type: Option<String>,
+    pub use_rust_decimal: Option<bool>,
+    pub decimal_type: Option<String>,
+    pub singularize_table_names: Option<bool>,
+}
+
+impl OutputSpec {
+    /// Options of this output on top of the command-line ones
+    pub fn apply(&self, options: &Options) -> Options {
+        let mut options = options.clone();
+        if let Some(postgres_crate) = &self.postgres_crate {
+            options.postgres_crate = postgres_crate.clone();
+        }
+        if let Some(crate_version) = &self.crate_version {
+            options.crate_version = crate_version.clone();
+        }
+        if let Some(output_format) = &self.output_format {
+            options.output_format = output_format.clone();
+        }
+        options.serde = self.serde.unwrap_or(options.serde);
+        options.use_chrono_crate = self.use_chrono_crate.unwrap_or(options.use_chrono_crate);
+        if let Some(timestamp_type) = &self.timestamp_type {
+            // use_chrono_crate of the command line would take precedence otherwise
+            options.use_chrono_crate = self.use_chrono_crate.unwrap_or(false);
+            options.timestamp_type = timestamp_type.clone();
+        }
+        options.use_rust_decimal = self.use_rust_decimal.unwrap_or(options.use_rust_decimal);
+        if let Some(decimal_type) = &self.decimal_type {
+            options.use_rust_decimal = self.use_rust_decimal.unwrap_or(false);
+            options.decimal_type = decimal_type.clone();
+        }
+        options.singularize_table_names = self.singularize_table_names.unwrap_or(options.singularize_table_names);
+        options
+    }
+}
+
+/// Parses an outputs file in a form
+/// `[[outputs]]` `output_file = "src/sqlx.rs"` `postgres_crate = "sqlx"`
+pub fn parse_outputs(content: &str) -> Result<Vec<OutputSpec>, Error> {
+    let value: toml::Value = toml::from_str(content)
+        .map_err(|e| Error::InvalidConfig(e.to_string()))?;
+    let outputs = as_table(&value, "outputs file")?.get("outputs")
+        .and_then(| outputs | outputs.as_array())
+        .ok_or_else(|| Error::InvalidConfig(String::from("outputs: expected an [[outputs]] array")))?;
+    outputs.iter().enumerate().map(| (i, output) | {
+        let name = format!("outputs[{}]", i);
+        let mut spec = OutputSpec::default();
+        for (key, value) in as_table(output, &name)? {
+            match key.as_str() {
+                "output_file" => spec.output_file = as_str(value, &name, key)?,
+                "postgres_crate" => spec.postgres_crate = Some(as_one_of(value, &name, key,
+                    &[CRATE_POSTGRES, CRATE_SQLX, CRATE_TOKIO_POSTGRES])?),
+                "crate_version" => spec.crate_version = Some(as_one_of(value, &name, key, &CRATE_VERSIONS)?),
+                "output_format" => spec.output_format = Some(as_one_of(value, &name, key,
+                    &[OUTPUT_RUST, OUTPUT_JSON, OUTPUT_SQL])?),
+                "serde" => spec.serde = Some(as_bool(value, &name, key)?),
+                "use_chrono_crate" => spec.use_chrono_crate = Some(as_bool(value, &name, key)?),
+                "timestamp_type" => spec.timestamp_type = Some(as_one_of(value, &name, key,
+                    &[TIMESTAMP_STRING, TIMESTAMP_CHRONO, TIMESTAMP_TIME])?),
+                "use_rust_decimal" => spec.use_rust_decimal = Some(as_bool(value, &name, key)?),
+                "decimal_type" => spec.decimal_type = Some(as_one_of(value, &name, key,
+                    &[DECIMAL_STRING, DECIMAL_RUST_DECIMAL, DECIMAL_BIGDECIMAL])?),
+                "singularize_table_names" => spec.singularize_table_names = Some(as_bool(value, &name, key)?),
+                _ => return Err(Error::InvalidConfig(format!("{}: unknown option '{}'", name, key)))
+            }
+        }
+        if spec.output_file.is_empty() {
+            return Err(Error::InvalidConfig(format!("{}: output_file is required", name)));
+        }
+        Ok(spec)
+    }).collect()
+}
+
+fn as_str(value: &toml::Value, name: &str, key: &str) -> Result<String, Error> {
+    value.as_str().map(String::from).ok_or_else(|| Error::InvalidConfig(
+        format!("{}.{}: expected a string", name, key)))
+}
+
+fn as_one_of(value: &toml::Value, name: &str, key: &str, allowed: &[&str]) -> Result<String, Error> {
+    let value = as_str(value, name, key)?;
+    if allowed.contains(&value.as_str()) {
+        Ok(value)
+    } else {
+        Err(Error::InvalidConfig(format!("{}.{}: expected one of {}", name, key, allowed.join(", "))))
+    }
+}
+
+fn as_bool(value: &toml::Value, name: &str, key: &str) -> Result<bool, Error> {
+    value.as_bool().ok_or_else(|| Error::InvalidConfig(
+        format!("{}.{}: expected a boolean", name, key)))
+}
+
+fn as_table<'a>(value: &'a toml::Value, name: &str) -> Result<&'a toml::value::Table, Error> {
+    value.as_table().ok_or_else(|| Error::InvalidConfig(
+        format!("{}: expected a table", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn singular_rules_map_plurals() {
+        let rules = parse_singular_rules("people = \"person\"\nmice = \"mouse\"").unwrap();
+        assert_eq!(rules.get("people").map(String::as_str), Some("person"));
+        assert_eq!(rules.get("mice").map(String::as_str), Some("mouse"));
+    }
+
+    #[test]
+    fn singular_rules_require_strings() {
+        assert!(matches!(parse_singular_rules("people = 1"), Err(Error::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn smallint_enums_parse_values_beyond_smallint() {
+        let enums = parse_smallint_enums("users.state = { 0 = \"Inactive\", 70000 = \"Active\" }").unwrap();
+        let state = &enums["users"]["state"];
+        assert_eq!(state.variants, vec![(0, String::from("Inactive")), (70000, String::from("Active"))]);
+    }
+
+    #[test]
+    fn smallint_enums_require_integer_values_and_string_labels() {
+        assert!(matches!(parse_smallint_enums("users.state = { one = \"Active\" }"), Err(Error::InvalidConfig(_))));
+        assert!(matches!(parse_smallint_enums("users.state = { 1 = 1 }"), Err(Error::InvalidConfig(_))));
+    }
+}